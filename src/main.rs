@@ -20,6 +20,28 @@ impl SimplePluginCommand for FromVdf {
         let mut signature = PluginSignature::build(self.name());
         signature.sig = signature.sig
             .switch("lossy", "Allow lossy parsing", Some('l'))
+            .switch(
+                "merge-duplicates",
+                "When a key repeats, keep only the last value instead of collecting all of them into a list",
+                Some('m'),
+            )
+            .switch(
+                "infer-types",
+                "Coerce string values that look like an int, float, or bool into that type",
+                None,
+            )
+            .named(
+                "platform",
+                nu_protocol::SyntaxShape::String,
+                "Platform tag (e.g. WIN32, OSX, LINUX) used to evaluate [$PLATFORM] conditions; entries whose condition doesn't match are dropped",
+                Some('p'),
+            )
+            .named(
+                "base-dir",
+                nu_protocol::SyntaxShape::String,
+                "Directory #base/#include paths are resolved against; without it those directives are parsed but not followed",
+                None,
+            )
             .input_output_types(vec![(nu_protocol::Type::String, nu_protocol::Type::Record(vec![].into()))])
             .category(Category::Formats);
         signature.sig
@@ -63,11 +85,186 @@ impl SimplePluginCommand for FromVdf {
         input: &Value,
     ) -> Result<Value, LabeledError> {
         let span = call.head;
-        let lossy = call.has_flag("lossy")?;
+        let infer_types = call.has_flag("infer-types")?;
+        let platform = call.get_flag::<String>("platform")?;
+        let base_dir = call.get_flag::<String>("base-dir")?;
+        let opts = vdf_value::ParseOptions {
+            lossy: call.has_flag("lossy")?,
+            merge_duplicates: call.has_flag("merge-duplicates")?,
+            platform: platform.as_deref(),
+            base_dir: base_dir.as_deref().map(std::path::Path::new),
+        };
+        let input_span = input.span();
         let input_string = input.as_str()?;
-        match vdf_value::parse(&input_string, lossy) {
-            Ok(vdf) => Ok(vdf.into_value(span)),
-            Err(e) => Err(LabeledError::new(e).with_label("Error parsing VDF", span)),
+        match vdf_value::parse(&input_string, &opts) {
+            Ok(vdf) => Ok(vdf.into_value(span, infer_types)),
+            Err(e) => {
+                let (line, column) = e.span.line_col(&input_string);
+                // `input_span` is the provenance span of the piped-in string
+                // expression, which only happens to equal the string's byte
+                // length for a literal; for `open file | from vdf` (or any
+                // computed string) it's far shorter than the content, so
+                // offsetting by it can land outside `input_span` entirely.
+                // Clamp to its bounds rather than handing nu a span that
+                // doesn't correspond to any real source text.
+                let start = (input_span.start + e.span.start).min(input_span.end);
+                let end = (input_span.start + e.span.end).min(input_span.end);
+                let error_span = nu_protocol::Span::new(start, end.max(start));
+                Err(LabeledError::new(format!("{} (line {line}, column {column})", e.message))
+                    .with_label("Error parsing VDF", error_span))
+            }
+        }
+    }
+}
+
+struct ToVdf;
+
+impl SimplePluginCommand for ToVdf {
+    type Plugin = VdfPlugin;
+
+    fn name(&self) -> &str {
+        "to vdf"
+    }
+
+    fn description(&self) -> &str {
+        "Serialize a structured value into VDF text."
+    }
+
+    fn signature(&self) -> Signature {
+        let mut signature = PluginSignature::build(self.name());
+        signature.sig = signature.sig
+            .switch("escape", "Escape embedded quotes and backslashes", Some('e'))
+            .named(
+                "indent",
+                nu_protocol::SyntaxShape::String,
+                "Indentation string used for each nesting level (defaults to a tab)",
+                Some('i'),
+            )
+            .input_output_types(vec![
+                (nu_protocol::Type::Record(vec![].into()), nu_protocol::Type::String),
+                (nu_protocol::Type::String, nu_protocol::Type::String),
+            ])
+            .category(Category::Formats);
+        signature.sig
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                example: r#"{Key: "Value"} | to vdf"#,
+                description: "Serialize a simple record into VDF",
+                result: Some(Value::test_string("\"Key\" \"Value\"\n")),
+            },
+            Example {
+                example: r#""Value" | to vdf"#,
+                description: "Serialize a bare string into a quoted VDF leaf",
+                result: Some(Value::test_string("\"Value\"")),
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _plugin: &VdfPlugin,
+        _engine: &nu_plugin::EngineInterface,
+        call: &EvaluatedCall,
+        input: &Value,
+    ) -> Result<Value, LabeledError> {
+        let span = call.head;
+        let escape = call.has_flag("escape")?;
+        let indent = call
+            .get_flag::<String>("indent")?
+            .unwrap_or_else(|| "\t".to_string());
+        match vdf_value::VdfValue::from_value(input) {
+            Ok(vdf) => Ok(Value::string(vdf_value::to_string(&vdf, &indent, escape), span)),
+            Err(e) => Err(LabeledError::new(e).with_label("Error serializing VDF", span)),
+        }
+    }
+}
+
+struct FromVdfBinary;
+
+impl SimplePluginCommand for FromVdfBinary {
+    type Plugin = VdfPlugin;
+
+    fn name(&self) -> &str {
+        "from vdf-binary"
+    }
+
+    fn description(&self) -> &str {
+        "Parse a binary KeyValues (VDF) blob into a structured value."
+    }
+
+    fn signature(&self) -> Signature {
+        let mut signature = PluginSignature::build(self.name());
+        signature.sig = signature.sig
+            .switch(
+                "merge-duplicates",
+                "When a key repeats, keep only the last value instead of collecting all of them into a list",
+                Some('m'),
+            )
+            .input_output_types(vec![(nu_protocol::Type::Binary, nu_protocol::Type::Record(vec![].into()))])
+            .category(Category::Formats);
+        signature.sig
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![]
+    }
+
+    fn run(
+        &self,
+        _plugin: &VdfPlugin,
+        _engine: &nu_plugin::EngineInterface,
+        call: &EvaluatedCall,
+        input: &Value,
+    ) -> Result<Value, LabeledError> {
+        let span = call.head;
+        let merge_duplicates = call.has_flag("merge-duplicates")?;
+        let bytes = input.as_binary()?;
+        match vdf_value::parse_binary(bytes, merge_duplicates) {
+            Ok(vdf) => Ok(vdf.into_value(span, false)),
+            Err(e) => Err(LabeledError::new(e).with_label("Error parsing binary VDF", span)),
+        }
+    }
+}
+
+struct ToVdfBinary;
+
+impl SimplePluginCommand for ToVdfBinary {
+    type Plugin = VdfPlugin;
+
+    fn name(&self) -> &str {
+        "to vdf-binary"
+    }
+
+    fn description(&self) -> &str {
+        "Serialize a structured value into a binary KeyValues (VDF) blob."
+    }
+
+    fn signature(&self) -> Signature {
+        let mut signature = PluginSignature::build(self.name());
+        signature.sig = signature.sig
+            .input_output_types(vec![(nu_protocol::Type::Record(vec![].into()), nu_protocol::Type::Binary)])
+            .category(Category::Formats);
+        signature.sig
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![]
+    }
+
+    fn run(
+        &self,
+        _plugin: &VdfPlugin,
+        _engine: &nu_plugin::EngineInterface,
+        call: &EvaluatedCall,
+        input: &Value,
+    ) -> Result<Value, LabeledError> {
+        let span = call.head;
+        match vdf_value::VdfValue::from_value(input) {
+            Ok(vdf) => Ok(Value::binary(vdf_value::write_binary(&vdf), span)),
+            Err(e) => Err(LabeledError::new(e).with_label("Error serializing binary VDF", span)),
         }
     }
 }
@@ -80,7 +277,12 @@ impl Plugin for VdfPlugin {
     }
 
     fn commands(&self) -> Vec<Box<dyn nu_plugin::PluginCommand<Plugin = Self>>> {
-        vec![Box::new(FromVdf)]
+        vec![
+            Box::new(FromVdf),
+            Box::new(ToVdf),
+            Box::new(FromVdfBinary),
+            Box::new(ToVdfBinary),
+        ]
     }
 }
 
@@ -90,18 +292,18 @@ fn main() {
 
 #[cfg(test)]
 mod tests {
-    use super::vdf_value::{parse, VdfValue};
-    use std::collections::BTreeMap;
+    use super::vdf_value::{parse, parse_binary, to_string, write_binary, ParseOptions, VdfValue};
+    use nu_protocol::{Span, Value};
+
+    fn opts(merge_duplicates: bool) -> ParseOptions<'static> {
+        ParseOptions { merge_duplicates, ..Default::default() }
+    }
 
     #[test]
     fn test_parse_simple_vdf() {
         let input = r#""Key" "Value""#;
-        let expected = VdfValue::Table({
-            let mut map = BTreeMap::new();
-            map.insert("Key".to_string(), VdfValue::Value("Value".to_string()));
-            map
-        });
-        assert_eq!(parse(input, false).unwrap(), expected);
+        let expected = VdfValue::Table(vec![("Key".to_string(), VdfValue::Value("Value".to_string()))]);
+        assert_eq!(parse(input, &opts(false)).unwrap(), expected);
     }
 
     #[test]
@@ -115,20 +317,21 @@ mod tests {
     }
     "SubKey3" "Value3"
 }"#;
-        let expected = VdfValue::Table({
-            let mut root_map = BTreeMap::new();
-            let mut sub_map = BTreeMap::new();
-            let mut nested_map = BTreeMap::new();
-
-            nested_map.insert("NestedKey".to_string(), VdfValue::Value("NestedValue".to_string()));
-            sub_map.insert("SubKey1".to_string(), VdfValue::Value("Value1".to_string()));
-            sub_map.insert("SubKey2".to_string(), VdfValue::Table(nested_map));
-            sub_map.insert("SubKey3".to_string(), VdfValue::Value("Value3".to_string()));
-
-            root_map.insert("RootKey".to_string(), VdfValue::Table(sub_map));
-            root_map
-        });
-        assert_eq!(parse(input, false).unwrap(), expected);
+        let expected = VdfValue::Table(vec![(
+            "RootKey".to_string(),
+            VdfValue::Table(vec![
+                ("SubKey1".to_string(), VdfValue::Value("Value1".to_string())),
+                (
+                    "SubKey2".to_string(),
+                    VdfValue::Table(vec![(
+                        "NestedKey".to_string(),
+                        VdfValue::Value("NestedValue".to_string()),
+                    )]),
+                ),
+                ("SubKey3".to_string(), VdfValue::Value("Value3".to_string())),
+            ]),
+        )]);
+        assert_eq!(parse(input, &opts(false)).unwrap(), expected);
     }
 
     #[test]
@@ -143,19 +346,339 @@ mod tests {
     }
     "SubKey3" "Value3"
 }"#;
-        let expected = VdfValue::Table({
-            let mut root_map = BTreeMap::new();
-            let mut sub_map = BTreeMap::new();
-            let mut nested_map = BTreeMap::new();
-
-            nested_map.insert("NestedKey".to_string(), VdfValue::Value("NestedValue".to_string()));
-            sub_map.insert("SubKey1".to_string(), VdfValue::Value("Value1".to_string()));
-            sub_map.insert("SubKey2".to_string(), VdfValue::Table(nested_map));
-            sub_map.insert("SubKey3".to_string(), VdfValue::Value("Value3".to_string()));
-
-            root_map.insert("RootKey".to_string(), VdfValue::Table(sub_map));
-            root_map
+        let expected = VdfValue::Table(vec![(
+            "RootKey".to_string(),
+            VdfValue::Table(vec![
+                ("SubKey1".to_string(), VdfValue::Value("Value1".to_string())),
+                (
+                    "SubKey2".to_string(),
+                    VdfValue::Table(vec![(
+                        "NestedKey".to_string(),
+                        VdfValue::Value("NestedValue".to_string()),
+                    )]),
+                ),
+                ("SubKey3".to_string(), VdfValue::Value("Value3".to_string())),
+            ]),
+        )]);
+        assert_eq!(parse(input, &opts(false)).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_parse_preserves_duplicate_key_order() {
+        let input = r#""Root"
+{
+    "Tag" "a"
+    "Tag" "b"
+    "Other" "c"
+}"#;
+        let expected = VdfValue::Table(vec![(
+            "Root".to_string(),
+            VdfValue::Table(vec![
+                ("Tag".to_string(), VdfValue::Value("a".to_string())),
+                ("Tag".to_string(), VdfValue::Value("b".to_string())),
+                ("Other".to_string(), VdfValue::Value("c".to_string())),
+            ]),
+        )]);
+        assert_eq!(parse(input, &opts(false)).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_parse_merge_duplicates_keeps_last() {
+        let input = r#""Root"
+{
+    "Tag" "a"
+    "Tag" "b"
+}"#;
+        let expected = VdfValue::Table(vec![(
+            "Root".to_string(),
+            VdfValue::Table(vec![("Tag".to_string(), VdfValue::Value("b".to_string()))]),
+        )]);
+        assert_eq!(parse(input, &opts(true)).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_to_string_simple_vdf() {
+        let vdf = VdfValue::Table(vec![("Key".to_string(), VdfValue::Value("Value".to_string()))]);
+        assert_eq!(to_string(&vdf, "\t", false), "\"Key\" \"Value\"\n");
+    }
+
+    #[test]
+    fn test_to_string_roundtrip_nested_vdf() {
+        let input = r#""RootKey"
+{
+    "SubKey1" "Value1"
+    "SubKey2"
+    {
+        "NestedKey" "NestedValue"
+    }
+}"#;
+        let parsed = parse(input, &opts(false)).unwrap();
+        let rendered = to_string(&parsed, "\t", false);
+        assert_eq!(parse(&rendered, &opts(false)).unwrap(), parsed);
+    }
+
+    #[test]
+    fn test_from_value_nested_record() {
+        let value = Value::test_record(nu_protocol::record! {
+            "Outer" => Value::test_record(nu_protocol::record! {
+                "Inner" => Value::test_string("Value"),
+            }),
+        });
+        let expected = VdfValue::Table(vec![(
+            "Outer".to_string(),
+            VdfValue::Table(vec![("Inner".to_string(), VdfValue::Value("Value".to_string()))]),
+        )]);
+        assert_eq!(VdfValue::from_value(&value).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_from_value_expands_list_field_into_repeated_entries() {
+        let value = Value::test_record(nu_protocol::record! {
+            "Tag" => Value::list(
+                vec![Value::test_string("a"), Value::test_string("b")],
+                Span::test_data(),
+            ),
         });
-        assert_eq!(parse(input, false).unwrap(), expected);
+        let expected = VdfValue::Table(vec![
+            ("Tag".to_string(), VdfValue::Value("a".to_string())),
+            ("Tag".to_string(), VdfValue::Value("b".to_string())),
+        ]);
+        assert_eq!(VdfValue::from_value(&value).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_from_value_rejects_unsupported_type() {
+        let value = Value::binary(vec![1, 2, 3], Span::test_data());
+        let err = VdfValue::from_value(&value).unwrap_err();
+        assert!(err.contains("Cannot convert"), "unexpected error: {err}");
+    }
+
+    #[test]
+    fn test_parse_binary_vdf() {
+        // { "RootKey" { "Width" 1920i32 "Name" "value" } }
+        let mut input = Vec::new();
+        input.push(0x00); // nested object
+        input.extend_from_slice(b"RootKey\0");
+        input.push(0x02); // int32
+        input.extend_from_slice(b"Width\0");
+        input.extend_from_slice(&1920i32.to_le_bytes());
+        input.push(0x01); // string
+        input.extend_from_slice(b"Name\0");
+        input.extend_from_slice(b"value\0");
+        input.push(0x08); // end RootKey
+        input.push(0x08); // end root
+
+        let expected = VdfValue::Table(vec![(
+            "RootKey".to_string(),
+            VdfValue::Table(vec![
+                ("Width".to_string(), VdfValue::Int(1920)),
+                ("Name".to_string(), VdfValue::Value("value".to_string())),
+            ]),
+        )]);
+        assert_eq!(parse_binary(&input, false).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_binary_roundtrip() {
+        let vdf = VdfValue::Table(vec![
+            ("Count".to_string(), VdfValue::Int(42)),
+            ("Label".to_string(), VdfValue::Value("ok".to_string())),
+        ]);
+        let bytes = write_binary(&vdf);
+        assert_eq!(parse_binary(&bytes, false).unwrap(), vdf);
+    }
+
+    #[test]
+    fn test_binary_int_outside_i32_range_widens_to_uint64_instead_of_truncating() {
+        let vdf = VdfValue::Table(vec![("Id".to_string(), VdfValue::Int(9_999_999_999))]);
+        let bytes = write_binary(&vdf);
+        let expected = VdfValue::Table(vec![("Id".to_string(), VdfValue::UInt64(9_999_999_999))]);
+        assert_eq!(parse_binary(&bytes, false).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_binary_uint64_roundtrips_through_value_and_back() {
+        let vdf = VdfValue::Table(vec![("Id".to_string(), VdfValue::UInt64(9_999_999_999))]);
+        let value = vdf.clone().into_value(Span::test_data(), false);
+        let rebuilt = VdfValue::from_value(&value).unwrap();
+        let bytes = write_binary(&rebuilt);
+        assert_eq!(parse_binary(&bytes, false).unwrap(), vdf);
+    }
+
+    #[test]
+    fn test_into_value_infer_types() {
+        let input = r#""Root"
+{
+    "Width" "1920"
+    "Enabled" "true"
+    "Ratio" "1.5"
+    "Name" "not a number"
+}"#;
+        let value = parse(input, &opts(false)).unwrap().into_value(Span::test_data(), true);
+        let record = value.as_record().unwrap().get("Root").unwrap().as_record().unwrap();
+        assert_eq!(record.get("Width"), Some(&Value::test_int(1920)));
+        assert_eq!(record.get("Enabled"), Some(&Value::test_bool(true)));
+        assert_eq!(record.get("Ratio"), Some(&Value::test_float(1.5)));
+        assert_eq!(record.get("Name"), Some(&Value::test_string("not a number")));
+    }
+
+    #[test]
+    fn test_into_value_infer_types_prefers_int_over_bool_for_bare_0_and_1() {
+        let input = r#""Root"
+{
+    "Count" "0"
+    "Version" "1"
+    "Enabled" "true"
+    "Disabled" "false"
+}"#;
+        let value = parse(input, &opts(false)).unwrap().into_value(Span::test_data(), true);
+        let record = value.as_record().unwrap().get("Root").unwrap().as_record().unwrap();
+        assert_eq!(record.get("Count"), Some(&Value::test_int(0)));
+        assert_eq!(record.get("Version"), Some(&Value::test_int(1)));
+        assert_eq!(record.get("Enabled"), Some(&Value::test_bool(true)));
+        assert_eq!(record.get("Disabled"), Some(&Value::test_bool(false)));
+    }
+
+    #[test]
+    fn test_into_value_without_infer_types_keeps_strings() {
+        let input = r#""Key" "1920""#;
+        let value = parse(input, &opts(false)).unwrap().into_value(Span::test_data(), false);
+        let record = value.as_record().unwrap();
+        assert_eq!(record.get("Key"), Some(&Value::test_string("1920")));
+    }
+
+    #[test]
+    fn test_platform_condition_drops_non_matching_entries() {
+        let input = r#""Root"
+{
+    "Windows" "a" [$WIN32]
+    "Mac" "b" [$OSX]
+    "Both" "c" [$WIN32||$OSX]
+    "NotWindows" "d" [!$WIN32]
+}"#;
+        let parsed = parse(
+            input,
+            &ParseOptions { platform: Some("WIN32"), ..Default::default() },
+        )
+        .unwrap();
+        let expected = VdfValue::Table(vec![(
+            "Root".to_string(),
+            VdfValue::Table(vec![
+                ("Windows".to_string(), VdfValue::Value("a".to_string())),
+                ("Both".to_string(), VdfValue::Value("c".to_string())),
+            ]),
+        )]);
+        assert_eq!(parsed, expected);
+    }
+
+    #[test]
+    fn test_platform_condition_ignored_without_platform_option() {
+        let input = r#""Key" "Value" [$WIN32]"#;
+        let expected = VdfValue::Table(vec![("Key".to_string(), VdfValue::Value("Value".to_string()))]);
+        assert_eq!(parse(input, &opts(false)).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_platform_condition_between_key_and_block_drops_non_matching_blocks() {
+        let input = r#""Root"
+{
+    "Panel" [$WIN32]
+    {
+        "Width" "100"
+    }
+    "Panel" [$OSX]
+    {
+        "Width" "200"
+    }
+}"#;
+        let parsed = parse(
+            input,
+            &ParseOptions { platform: Some("WIN32"), ..Default::default() },
+        )
+        .unwrap();
+        let expected = VdfValue::Table(vec![(
+            "Root".to_string(),
+            VdfValue::Table(vec![(
+                "Panel".to_string(),
+                VdfValue::Table(vec![("Width".to_string(), VdfValue::Value("100".to_string()))]),
+            )]),
+        )]);
+        assert_eq!(parsed, expected);
+    }
+
+    #[test]
+    fn test_include_directive_merges_file_with_later_keys_winning() {
+        let dir = std::env::temp_dir().join(format!(
+            "nu_plugin_vdf_test_include_{:?}",
+            std::thread::current().id()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("extra.vdf"), r#""Other" "fromInclude" "Shared" "fromInclude""#).unwrap();
+
+        let input = r#""Shared" "fromRoot"
+#include "extra.vdf""#;
+        let parsed = parse(
+            input,
+            &ParseOptions { base_dir: Some(&dir), ..Default::default() },
+        )
+        .unwrap();
+        let expected = VdfValue::Table(vec![
+            ("Shared".to_string(), VdfValue::Value("fromInclude".to_string())),
+            ("Other".to_string(), VdfValue::Value("fromInclude".to_string())),
+        ]);
+        assert_eq!(parsed, expected);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_base_directive_followed_by_local_key_overrides_without_merge_duplicates() {
+        let dir = std::env::temp_dir().join(format!(
+            "nu_plugin_vdf_test_base_override_{:?}",
+            std::thread::current().id()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("default.vdf"), r#""Width" "800""#).unwrap();
+
+        let input = r#"#base "default.vdf"
+"Width" "1920""#;
+        let parsed = parse(
+            input,
+            &ParseOptions { base_dir: Some(&dir), ..Default::default() },
+        )
+        .unwrap();
+        let expected = VdfValue::Table(vec![("Width".to_string(), VdfValue::Value("1920".to_string()))]);
+        assert_eq!(parsed, expected);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_include_directive_without_base_dir_is_noop() {
+        let input = r#""Key" "Value"
+#include "missing.vdf""#;
+        let expected = VdfValue::Table(vec![("Key".to_string(), VdfValue::Value("Value".to_string()))]);
+        assert_eq!(parse(input, &opts(false)).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_include_directive_cycle_is_reported_instead_of_overflowing_the_stack() {
+        let dir = std::env::temp_dir().join(format!(
+            "nu_plugin_vdf_test_include_cycle_{:?}",
+            std::thread::current().id()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("a.vdf"), r#"#include "b.vdf""#).unwrap();
+        std::fs::write(dir.join("b.vdf"), r#"#include "a.vdf""#).unwrap();
+
+        let input = r#"#include "a.vdf""#;
+        let err = parse(
+            input,
+            &ParseOptions { base_dir: Some(&dir), ..Default::default() },
+        )
+        .unwrap_err();
+        assert!(err.message.contains("cycle"), "unexpected error: {}", err.message);
+
+        std::fs::remove_dir_all(&dir).unwrap();
     }
 }
@@ -1,58 +1,531 @@
 use nu_protocol::{Span, Value};
-use std::collections::BTreeMap;
+use std::collections::HashMap;
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum VdfValue {
-    Table(BTreeMap<String, VdfValue>),
+    /// Entries in source order. Duplicate keys are kept as separate entries
+    /// rather than collapsed, so callers decide how to handle them.
+    Table(Vec<(String, VdfValue)>),
     Value(String),
+    /// A typed leaf as produced by the binary KeyValues encoding (type byte 0x02).
+    Int(i64),
+    /// A typed leaf as produced by the binary KeyValues encoding (type byte 0x03).
+    Float(f64),
+    /// A typed leaf as produced by the binary KeyValues encoding (type byte 0x07).
+    UInt64(u64),
 }
 
 impl VdfValue {
-    pub fn into_value(self, span: Span) -> Value {
+    /// Insert an entry into a table, honoring VDF's duplicate-key rules.
+    ///
+    /// When `merge_duplicates` is set (Valve's own default), a later key
+    /// overwrites the earlier entry in place, preserving its original
+    /// position. Otherwise every occurrence is kept and `into_value` groups
+    /// them into a `Value::list`.
+    fn insert(table: &mut Vec<(String, VdfValue)>, key: String, value: VdfValue, merge_duplicates: bool) {
+        if merge_duplicates {
+            if let Some(entry) = table.iter_mut().find(|(k, _)| *k == key) {
+                entry.1 = value;
+                return;
+            }
+        }
+        table.push((key, value));
+    }
+
+    /// Convert into a `nu_protocol::Value`. When `infer_types` is set, string
+    /// leaves that look like an integer, float, or bool are coerced to that
+    /// type; otherwise every `VdfValue::Value` leaf stays a plain string.
+    pub fn into_value(self, span: Span, infer_types: bool) -> Value {
         match self {
-            VdfValue::Table(map) => {
+            VdfValue::Table(entries) => {
+                let mut order = Vec::new();
+                let mut grouped: HashMap<String, Vec<VdfValue>> = HashMap::new();
+                for (k, v) in entries {
+                    if !grouped.contains_key(&k) {
+                        order.push(k.clone());
+                    }
+                    grouped.entry(k).or_default().push(v);
+                }
+
                 let mut record = nu_protocol::Record::new();
-                for (k, v) in map {
-                    record.push(k, v.into_value(span));
+                for k in order {
+                    let mut values = grouped.remove(&k).unwrap();
+                    if values.len() == 1 {
+                        record.push(k, values.remove(0).into_value(span, infer_types));
+                    } else {
+                        let list = values
+                            .into_iter()
+                            .map(|v| v.into_value(span, infer_types))
+                            .collect();
+                        record.push(k, Value::list(list, span));
+                    }
                 }
                 Value::record(record, span)
             }
+            VdfValue::Value(s) if infer_types => infer_scalar(&s, span),
             VdfValue::Value(s) => Value::string(s, span),
+            VdfValue::Int(i) => Value::int(i, span),
+            VdfValue::Float(f) => Value::float(f, span),
+            // nu_protocol has no unsigned 64-bit Value variant, so this is
+            // exact for u <= i64::MAX; `from_value` routes the result back
+            // to `UInt64` only when it is above i32::MAX (e.g. a Steam ID),
+            // so smaller values come back as `Int`.
+            VdfValue::UInt64(u) => Value::int(u as i64, span),
+        }
+    }
+
+    /// Build a `VdfValue` out of a `nu_protocol::Value`, the inverse of `into_value`.
+    ///
+    /// Records become tables and floats keep their type. `nu_protocol::Value`
+    /// has no unsigned 64-bit integer, so `Value::Int` is routed back to
+    /// `VdfValue::UInt64` when it is above `i32::MAX` (e.g. a Steam ID)
+    /// rather than `VdfValue::Int`. In a `from vdf-binary | to vdf-binary`
+    /// round-trip, a type 0x07 value above `i32::MAX` and up to `i64::MAX`
+    /// is therefore written back as 0x07; a smaller one comes back as an
+    /// int32 (0x02) entry, and one above `i64::MAX` does not survive the
+    /// trip through `Value::Int`. A negative value beyond `i32` has no
+    /// exact binary representation here. Bools become string
+    /// leaves since VDF has no native boolean type. A list field is expanded
+    /// back into repeated entries under the same key, undoing the grouping
+    /// `into_value` does for duplicate keys.
+    pub fn from_value(value: &Value) -> Result<VdfValue, String> {
+        match value {
+            Value::Record { val, .. } => {
+                let mut table = Vec::new();
+                for (k, v) in val.iter() {
+                    match v {
+                        Value::List { vals, .. } => {
+                            for item in vals {
+                                table.push((k.clone(), VdfValue::from_value(item)?));
+                            }
+                        }
+                        other => table.push((k.clone(), VdfValue::from_value(other)?)),
+                    }
+                }
+                Ok(VdfValue::Table(table))
+            }
+            Value::String { val, .. } => Ok(VdfValue::Value(val.clone())),
+            Value::Int { val, .. } => match u64::try_from(*val) {
+                Ok(u) if *val > i32::MAX as i64 => Ok(VdfValue::UInt64(u)),
+                _ => Ok(VdfValue::Int(*val)),
+            },
+            Value::Float { val, .. } => Ok(VdfValue::Float(*val)),
+            Value::Bool { val, .. } => Ok(VdfValue::Value(val.to_string())),
+            other => Err(format!("Cannot convert {} to VDF", other.get_type())),
         }
     }
 }
 
-pub fn parse(input: &str, lossy: bool) -> Result<VdfValue, String> {
-    let mut chars = input.chars().peekable();
-    skip_whitespace(&mut chars); // Skip leading whitespace
+/// Coerce a raw VDF string leaf into a typed `Value`, for `--infer-types`,
+/// trying int, then float, then bool, in that order.
+///
+/// `"0"`/`"1"` are far more often counts, IDs, or version numbers than
+/// flags, so they're caught by the integer check and come out as
+/// `Value::int`. Only the literal words `"true"`/`"false"` are treated as
+/// booleans; anything else falls back to a plain string.
+fn infer_scalar(s: &str, span: Span) -> Value {
+    if let Ok(i) = s.parse::<i64>() {
+        return Value::int(i, span);
+    }
+    if let Ok(f) = s.parse::<f64>() {
+        return Value::float(f, span);
+    }
+    match s {
+        "true" => Value::bool(true, span),
+        "false" => Value::bool(false, span),
+        _ => Value::string(s.to_string(), span),
+    }
+}
 
-    // VDF root is typically a single key-value pair, where the value can be a table.
-    if let Some(key) = parse_string(&mut chars, lossy)? {
-        if let Some(value) = parse_value(&mut chars, lossy)? {
-            let mut table = BTreeMap::new();
-            table.insert(key, value);
-            Ok(VdfValue::Table(table))
-        } else {
-            Err("Unexpected end of input: missing value for root key".to_string())
+/// Serialize a `VdfValue` into VDF text, using `indent` for each nesting level.
+///
+/// When `escape` is set, embedded quotes and backslashes in keys and values
+/// are re-escaped so the output can be parsed back by `from vdf`.
+pub fn to_string(value: &VdfValue, indent: &str, escape: bool) -> String {
+    let mut out = String::new();
+    match value {
+        VdfValue::Table(entries) => {
+            for (k, v) in entries {
+                write_entry(&mut out, k, v, 0, indent, escape);
+            }
+        }
+        VdfValue::Value(s) => {
+            out.push('"');
+            out.push_str(&escape_string(s, escape));
+            out.push('"');
+        }
+        VdfValue::Int(i) => out.push_str(&format!("\"{i}\"")),
+        VdfValue::Float(f) => out.push_str(&format!("\"{f}\"")),
+        VdfValue::UInt64(u) => out.push_str(&format!("\"{u}\"")),
+    }
+    out
+}
+
+fn write_entry(out: &mut String, key: &str, value: &VdfValue, depth: usize, indent: &str, escape: bool) {
+    let prefix = indent.repeat(depth);
+    match value {
+        VdfValue::Table(entries) => {
+            out.push_str(&prefix);
+            out.push('"');
+            out.push_str(&escape_string(key, escape));
+            out.push_str("\"\n");
+            out.push_str(&prefix);
+            out.push_str("{\n");
+            for (k, v) in entries {
+                write_entry(out, k, v, depth + 1, indent, escape);
+            }
+            out.push_str(&prefix);
+            out.push_str("}\n");
         }
+        VdfValue::Value(s) => write_scalar_entry(out, &prefix, key, s, escape),
+        VdfValue::Int(i) => write_scalar_entry(out, &prefix, key, &i.to_string(), escape),
+        VdfValue::Float(f) => write_scalar_entry(out, &prefix, key, &f.to_string(), escape),
+        VdfValue::UInt64(u) => write_scalar_entry(out, &prefix, key, &u.to_string(), escape),
+    }
+}
+
+fn write_scalar_entry(out: &mut String, prefix: &str, key: &str, value: &str, escape: bool) {
+    out.push_str(prefix);
+    out.push('"');
+    out.push_str(&escape_string(key, escape));
+    out.push_str("\" \"");
+    out.push_str(&escape_string(value, escape));
+    out.push_str("\"\n");
+}
+
+fn escape_string(s: &str, escape: bool) -> String {
+    if escape {
+        s.replace('\\', "\\\\").replace('"', "\\\"")
     } else {
-        Err("Unexpected end of input: missing root key".to_string())
+        s.to_string()
+    }
+}
+
+/// A byte-offset range into the original input, used to locate parse errors.
+///
+/// Named distinctly from `nu_protocol::Span` since that one addresses
+/// positions in Nu's own source registry, not offsets within a VDF string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ByteSpan {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl ByteSpan {
+    /// 1-based (line, column) of `start` within `input`, for human-readable diagnostics.
+    pub fn line_col(&self, input: &str) -> (usize, usize) {
+        let mut line = 1;
+        let mut col = 1;
+        for c in input[..self.start.min(input.len())].chars() {
+            if c == '\n' {
+                line += 1;
+                col = 1;
+            } else {
+                col += 1;
+            }
+        }
+        (line, col)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub message: String,
+    pub span: ByteSpan,
+}
+
+/// Knobs that affect parsing but aren't part of the VDF grammar itself.
+#[derive(Debug, Clone, Default)]
+pub struct ParseOptions<'a> {
+    pub lossy: bool,
+    pub merge_duplicates: bool,
+    /// Platform tag (e.g. `WIN32`) used to evaluate `[$PLATFORM]` conditions.
+    /// When this is `None`, every entry is kept (none are filtered out) and
+    /// its condition tag is discarded.
+    pub platform: Option<&'a str>,
+    /// Directory `#base`/`#include` paths are resolved against. Directives
+    /// are parsed but left unresolved (no-op) when this is `None`.
+    pub base_dir: Option<&'a std::path::Path>,
+}
+
+pub fn parse(input: &str, opts: &ParseOptions) -> Result<VdfValue, ParseError> {
+    let mut chars = input.char_indices().peekable();
+    let mut include_stack = Vec::new();
+    let entries = parse_entries(&mut chars, input, opts, None, &mut include_stack)?;
+    Ok(VdfValue::Table(entries))
+}
+
+/// Parse a sequence of key-value pairs and `#base`/`#include` directives,
+/// either up to `closing` (used for a `{ ... }` block) or to EOF (the root).
+///
+/// `include_stack` holds the canonicalized paths of files currently being
+/// included, so `try_consume_directive` can detect `#include`/`#base` cycles
+/// instead of recursing without bound.
+fn parse_entries<I>(
+    chars: &mut std::iter::Peekable<I>,
+    input: &str,
+    opts: &ParseOptions,
+    closing: Option<char>,
+    include_stack: &mut Vec<std::path::PathBuf>,
+) -> Result<Vec<(String, VdfValue)>, ParseError>
+where
+    I: Iterator<Item = (usize, char)> + Clone,
+{
+    let mut table = Vec::new();
+    // Keys most recently introduced by a `#base`/`#include` directive in this
+    // scope, so the next local definition of the same key overrides it in
+    // place even when `merge_duplicates` is off (which would otherwise just
+    // append a second entry, since ordinary same-scope duplicates are kept
+    // side by side). Cleared for a key as soon as a local override consumes
+    // it, so any further repeats fall back to the usual duplicate handling.
+    let mut directive_keys: std::collections::HashSet<String> = std::collections::HashSet::new();
+    loop {
+        skip_whitespace(chars);
+        match chars.peek().copied() {
+            Some((_, c)) if closing == Some(c) => {
+                chars.next();
+                break;
+            }
+            None if closing.is_some() => {
+                let pos = input.len();
+                return Err(ParseError {
+                    message: "Unexpected end of input: unclosed table".to_string(),
+                    span: ByteSpan { start: pos, end: pos },
+                });
+            }
+            None => break,
+            _ => {}
+        }
+
+        if try_consume_directive(chars, input, opts, &mut table, include_stack, &mut directive_keys)? {
+            continue;
+        }
+
+        let key_start = current_pos(chars, input);
+        let key = match parse_string(chars, input, opts.lossy)? {
+            Some(key) => key,
+            None => match (chars.peek().copied(), closing) {
+                (Some((i, c)), Some(close)) => {
+                    return Err(ParseError {
+                        message: format!(
+                            "Unexpected token in table: expected key or '{close}', found '{c}'"
+                        ),
+                        span: ByteSpan { start: i, end: i + c.len_utf8() },
+                    });
+                }
+                (Some((i, c)), None) => {
+                    return Err(ParseError {
+                        message: format!("Unexpected token: expected a key, found '{c}'"),
+                        span: ByteSpan { start: i, end: i + c.len_utf8() },
+                    });
+                }
+                (None, _) => {
+                    return Err(ParseError {
+                        message: "Unexpected end of input: missing key".to_string(),
+                        span: ByteSpan { start: key_start, end: key_start },
+                    });
+                }
+            },
+        };
+
+        // A condition tag can sit between the key and its value (e.g.
+        // `"Panel" [$WIN32]\n{ ... }`), not just after the value, so check
+        // both positions.
+        let pre_value_condition = parse_condition(chars, input)?;
+
+        let value = match parse_value(chars, input, opts, include_stack)? {
+            Some(value) => value,
+            None => {
+                let pos = current_pos(chars, input);
+                return Err(ParseError {
+                    message: "Unexpected end of input: missing value".to_string(),
+                    span: ByteSpan { start: pos, end: pos },
+                });
+            }
+        };
+
+        let post_value_condition = parse_condition(chars, input)?;
+        let condition = pre_value_condition.or(post_value_condition);
+        let keep = match (&condition, opts.platform) {
+            (Some(cond), Some(platform)) => eval_condition(cond, platform),
+            _ => true,
+        };
+        if keep {
+            if directive_keys.remove(&key) {
+                // A local definition always overrides the value a directive
+                // contributed for this key, regardless of `merge_duplicates`.
+                match table.iter_mut().find(|(k, _)| *k == key) {
+                    Some(entry) => entry.1 = value,
+                    None => table.push((key, value)),
+                }
+            } else {
+                VdfValue::insert(&mut table, key, value, opts.merge_duplicates);
+            }
+        }
+    }
+    Ok(table)
+}
+
+/// Consume a `#base "path"` or `#include "path"` directive if one is next.
+///
+/// When `opts.base_dir` is set, the referenced file is read, parsed with the
+/// same options, and merged into `table` with later (included) keys
+/// overriding earlier ones. Without a base directory the directive is parsed
+/// but otherwise ignored, since there's nowhere to resolve it against. The
+/// file's canonical path is pushed onto `include_stack` for the duration of
+/// its parse so a file that (directly or transitively) includes itself is
+/// reported as a `ParseError` instead of recursing until the stack overflows.
+/// Every key the directive contributes is recorded in `directive_keys` so a
+/// later local definition of that key (the common `#base "default.vdf"`
+/// followed by a local override pattern) replaces it in place rather than
+/// becoming a second, sibling entry.
+fn try_consume_directive<I>(
+    chars: &mut std::iter::Peekable<I>,
+    input: &str,
+    opts: &ParseOptions,
+    table: &mut Vec<(String, VdfValue)>,
+    include_stack: &mut Vec<std::path::PathBuf>,
+    directive_keys: &mut std::collections::HashSet<String>,
+) -> Result<bool, ParseError>
+where
+    I: Iterator<Item = (usize, char)> + Clone,
+{
+    let hash_pos = match chars.peek().copied() {
+        Some((i, '#')) => i,
+        _ => return Ok(false),
+    };
+    chars.next(); // Consume '#'
+
+    let mut directive = String::new();
+    while let Some(&(_, c)) = chars.peek() {
+        if c.is_whitespace() {
+            break;
+        }
+        directive.push(c);
+        chars.next();
+    }
+
+    let arg = parse_string(chars, input, opts.lossy)?.ok_or_else(|| ParseError {
+        message: format!("Unexpected end of input: missing path for #{directive}"),
+        span: ByteSpan { start: hash_pos, end: hash_pos },
+    })?;
+
+    if directive != "base" && directive != "include" {
+        return Err(ParseError {
+            message: format!("Unknown preprocessor directive: #{directive}"),
+            span: ByteSpan { start: hash_pos, end: hash_pos + directive.len() + 1 },
+        });
+    }
+
+    if let Some(base_dir) = opts.base_dir {
+        let path = base_dir.join(&arg);
+        let contents = std::fs::read_to_string(&path).map_err(|e| ParseError {
+            message: format!("Failed to read '{}' for #{directive}: {e}", path.display()),
+            span: ByteSpan { start: hash_pos, end: hash_pos },
+        })?;
+        let canonical = std::fs::canonicalize(&path).unwrap_or_else(|_| path.clone());
+        if include_stack.contains(&canonical) {
+            return Err(ParseError {
+                message: format!(
+                    "Include cycle detected: '{}' is already being included",
+                    canonical.display()
+                ),
+                span: ByteSpan { start: hash_pos, end: hash_pos },
+            });
+        }
+
+        include_stack.push(canonical);
+        let mut included_chars = contents.char_indices().peekable();
+        let included = parse_entries(&mut included_chars, &contents, opts, None, include_stack);
+        include_stack.pop();
+        let included = included?;
+
+        for (k, v) in included {
+            if let Some(entry) = table.iter_mut().find(|(ek, _)| *ek == k) {
+                entry.1 = v;
+            } else {
+                table.push((k.clone(), v));
+            }
+            directive_keys.insert(k);
+        }
     }
+
+    Ok(true)
 }
 
-fn parse_string<I>(chars: &mut std::iter::Peekable<I>, lossy: bool) -> Result<Option<String>, String>
+/// Parse an optional trailing `[$PLATFORM]`-style condition tag, returning
+/// its (unparsed) inner expression.
+fn parse_condition<I>(chars: &mut std::iter::Peekable<I>, input: &str) -> Result<Option<String>, ParseError>
 where
-    I: Iterator<Item = char> + Clone,
+    I: Iterator<Item = (usize, char)> + Clone,
 {
     skip_whitespace(chars);
-    if chars.peek() != Some(&'"') {
+    if chars.peek().map(|&(_, c)| c) != Some('[') {
         return Ok(None);
     }
+    chars.next(); // Consume '['
+
+    let mut condition = String::new();
+    loop {
+        match chars.next() {
+            Some((_, ']')) => return Ok(Some(condition)),
+            Some((_, c)) => condition.push(c),
+            None => {
+                let pos = input.len();
+                return Err(ParseError {
+                    message: "Unexpected end of input: unclosed platform condition".to_string(),
+                    span: ByteSpan { start: pos, end: pos },
+                });
+            }
+        }
+    }
+}
+
+/// Evaluate a condition expression like `$WIN32`, `!$OSX`, or
+/// `$WIN32 && !$X360` against a single `platform` tag.
+fn eval_condition(condition: &str, platform: &str) -> bool {
+    condition.split("||").any(|and_group| {
+        and_group.split("&&").all(|term| {
+            let term = term.trim();
+            match term.strip_prefix('!') {
+                Some(rest) => !eval_condition_atom(rest, platform),
+                None => eval_condition_atom(term, platform),
+            }
+        })
+    })
+}
+
+fn eval_condition_atom(term: &str, platform: &str) -> bool {
+    term.trim()
+        .trim_start_matches('$')
+        .eq_ignore_ascii_case(platform)
+}
+
+fn current_pos<I>(chars: &mut std::iter::Peekable<I>, input: &str) -> usize
+where
+    I: Iterator<Item = (usize, char)>,
+{
+    chars.peek().map(|&(i, _)| i).unwrap_or(input.len())
+}
+
+fn parse_string<I>(
+    chars: &mut std::iter::Peekable<I>,
+    input: &str,
+    lossy: bool,
+) -> Result<Option<String>, ParseError>
+where
+    I: Iterator<Item = (usize, char)> + Clone,
+{
+    skip_whitespace(chars);
+    let start = match chars.peek() {
+        Some(&(i, '"')) => i,
+        _ => return Ok(None),
+    };
     chars.next(); // Consume opening quote
 
     let mut s = String::new();
     let mut escaped = false;
-    while let Some(&c) = chars.peek() {
+    while let Some(&(_, c)) = chars.peek() {
         match c {
             '"' if !escaped => {
                 chars.next(); // Consume closing quote
@@ -73,57 +546,43 @@ where
     if lossy {
         Ok(Some(s))
     } else {
-        Err("Unexpected end of input: unclosed string".to_string())
+        Err(ParseError {
+            message: "Unexpected end of input: unclosed string".to_string(),
+            span: ByteSpan { start, end: input.len() },
+        })
     }
 }
 
-fn parse_value<I>(chars: &mut std::iter::Peekable<I>, lossy: bool) -> Result<Option<VdfValue>, String>
+fn parse_value<I>(
+    chars: &mut std::iter::Peekable<I>,
+    input: &str,
+    opts: &ParseOptions,
+    include_stack: &mut Vec<std::path::PathBuf>,
+) -> Result<Option<VdfValue>, ParseError>
 where
-    I: Iterator<Item = char> + Clone,
+    I: Iterator<Item = (usize, char)> + Clone,
 {
     skip_whitespace(chars);
-    match chars.peek() {
-        Some('{') => {
+    match chars.peek().copied() {
+        Some((_, '{')) => {
             chars.next(); // Consume opening brace
-            let mut table = BTreeMap::new();
-            loop {
-                skip_whitespace(chars);
-                if chars.peek() == Some(&'}') {
-                    chars.next(); // Consume closing brace
-                    break;
-                }
-                if let Some(key) = parse_string(chars, lossy)? {
-                    if let Some(value) = parse_value(chars, lossy)? {
-                        table.insert(key, value);
-                    } else {
-                        return Err("Unexpected end of input: missing value".to_string());
-                    }
-                } else {
-                    // If no key is found, it might be an empty table or malformed.
-                    // If it's not '}', then it's an error.
-                    if chars.peek() != Some(&'}') {
-                        return Err("Unexpected token in table: expected key or '}'".to_string());
-                    }
-                }
-            }
-            Ok(Some(VdfValue::Table(table)))
-        }
-        Some('"') => {
-            parse_string(chars, lossy).map(|s| s.map(VdfValue::Value))
+            let entries = parse_entries(chars, input, opts, Some('}'), include_stack)?;
+            Ok(Some(VdfValue::Table(entries)))
         }
+        Some((_, '"')) => parse_string(chars, input, opts.lossy).map(|s| s.map(VdfValue::Value)),
         _ => Ok(None),
     }
 }
 
 fn skip_whitespace<I>(chars: &mut std::iter::Peekable<I>)
 where
-    I: Iterator<Item = char> + Clone,
+    I: Iterator<Item = (usize, char)> + Clone,
 {
     loop {
         let mut skipped_something = false;
 
         // Skip actual whitespace
-        while let Some(&c) = chars.peek() {
+        while let Some(&(_, c)) = chars.peek() {
             if c.is_whitespace() {
                 chars.next();
                 skipped_something = true;
@@ -133,14 +592,14 @@ where
         }
 
         // Skip single-line comments (//)
-        if let Some('/') = chars.peek() {
-            let mut temp_chars = chars.clone(); // Peekableをクローンして先読み
-            temp_chars.next(); // 最初の '/' を消費
-            if let Some('/') = temp_chars.peek() {
-                chars.next(); // 最初の '/' を消費
-                chars.next(); // 2番目の '/' を消費
-                while let Some(&c) = chars.peek() {
-                    if c == '\n' || c == '\r' { // \n と \r をエスケープ
+        if let Some(&(_, '/')) = chars.peek() {
+            let mut temp_chars = chars.clone();
+            temp_chars.next(); // Consume the first '/'
+            if let Some(&(_, '/')) = temp_chars.peek() {
+                chars.next(); // Consume the first '/'
+                chars.next(); // Consume the second '/'
+                while let Some(&(_, c)) = chars.peek() {
+                    if c == '\n' || c == '\r' {
                         break; // End of line
                     }
                     chars.next();
@@ -154,3 +613,144 @@ where
         }
     }
 }
+
+// Binary KeyValues ("vdf-binary"): a stream of typed nodes. Each node is a
+// type byte, a NUL-terminated key, and then either a NUL-terminated string or
+// a fixed-width little-endian number depending on the type. A nested object
+// (0x00) recurses until its own 0x08 end marker; the root ends at EOF or a
+// trailing 0x08.
+const BINARY_TYPE_TABLE: u8 = 0x00;
+const BINARY_TYPE_STRING: u8 = 0x01;
+const BINARY_TYPE_INT32: u8 = 0x02;
+const BINARY_TYPE_FLOAT32: u8 = 0x03;
+const BINARY_TYPE_UINT64: u8 = 0x07;
+const BINARY_TYPE_END: u8 = 0x08;
+
+pub fn parse_binary(input: &[u8], merge_duplicates: bool) -> Result<VdfValue, String> {
+    let mut pos = 0;
+    parse_binary_table(input, &mut pos, merge_duplicates)
+}
+
+fn parse_binary_table(input: &[u8], pos: &mut usize, merge_duplicates: bool) -> Result<VdfValue, String> {
+    let mut table = Vec::new();
+    loop {
+        if *pos >= input.len() {
+            break;
+        }
+        let type_byte = input[*pos];
+        *pos += 1;
+        if type_byte == BINARY_TYPE_END {
+            break;
+        }
+        let key = read_binary_cstring(input, pos)?;
+        let value = match type_byte {
+            BINARY_TYPE_TABLE => parse_binary_table(input, pos, merge_duplicates)?,
+            BINARY_TYPE_STRING => VdfValue::Value(read_binary_cstring(input, pos)?),
+            BINARY_TYPE_INT32 => VdfValue::Int(read_binary_i32(input, pos)? as i64),
+            BINARY_TYPE_FLOAT32 => VdfValue::Float(read_binary_f32(input, pos)? as f64),
+            BINARY_TYPE_UINT64 => VdfValue::UInt64(read_binary_u64(input, pos)?),
+            other => return Err(format!("Unknown binary VDF type byte: {other:#04x}")),
+        };
+        VdfValue::insert(&mut table, key, value, merge_duplicates);
+    }
+    Ok(VdfValue::Table(table))
+}
+
+fn read_binary_cstring(input: &[u8], pos: &mut usize) -> Result<String, String> {
+    let start = *pos;
+    while *pos < input.len() && input[*pos] != 0 {
+        *pos += 1;
+    }
+    if *pos >= input.len() {
+        return Err("Unexpected end of input: unterminated binary string".to_string());
+    }
+    let s = String::from_utf8_lossy(&input[start..*pos]).into_owned();
+    *pos += 1; // Consume the NUL terminator
+    Ok(s)
+}
+
+fn read_binary_bytes<'a>(input: &'a [u8], pos: &mut usize, len: usize) -> Result<&'a [u8], String> {
+    if *pos + len > input.len() {
+        return Err("Unexpected end of input: truncated binary field".to_string());
+    }
+    let bytes = &input[*pos..*pos + len];
+    *pos += len;
+    Ok(bytes)
+}
+
+fn read_binary_i32(input: &[u8], pos: &mut usize) -> Result<i32, String> {
+    let bytes = read_binary_bytes(input, pos, 4)?;
+    Ok(i32::from_le_bytes(bytes.try_into().unwrap()))
+}
+
+fn read_binary_f32(input: &[u8], pos: &mut usize) -> Result<f32, String> {
+    let bytes = read_binary_bytes(input, pos, 4)?;
+    Ok(f32::from_le_bytes(bytes.try_into().unwrap()))
+}
+
+fn read_binary_u64(input: &[u8], pos: &mut usize) -> Result<u64, String> {
+    let bytes = read_binary_bytes(input, pos, 8)?;
+    Ok(u64::from_le_bytes(bytes.try_into().unwrap()))
+}
+
+pub fn write_binary(value: &VdfValue) -> Vec<u8> {
+    let mut out = Vec::new();
+    if let VdfValue::Table(entries) = value {
+        write_binary_table(&mut out, entries);
+    }
+    out
+}
+
+fn write_binary_table(out: &mut Vec<u8>, entries: &[(String, VdfValue)]) {
+    for (k, v) in entries {
+        match v {
+            VdfValue::Table(inner) => {
+                out.push(BINARY_TYPE_TABLE);
+                write_binary_cstring(out, k);
+                write_binary_table(out, inner);
+            }
+            VdfValue::Value(s) => {
+                out.push(BINARY_TYPE_STRING);
+                write_binary_cstring(out, k);
+                write_binary_cstring(out, s);
+            }
+            VdfValue::Int(i) => {
+                // Most ints fit the type-0x02 int32 slot, but a value outside
+                // i32's range (e.g. a large Steam ID stuffed into an `Int`
+                // leaf by hand) would silently truncate there; widen it to
+                // the type-0x07 uint64 slot instead of wrapping.
+                if let Ok(i32_val) = i32::try_from(*i) {
+                    out.push(BINARY_TYPE_INT32);
+                    write_binary_cstring(out, k);
+                    out.extend_from_slice(&i32_val.to_le_bytes());
+                } else if let Ok(u64_val) = u64::try_from(*i) {
+                    out.push(BINARY_TYPE_UINT64);
+                    write_binary_cstring(out, k);
+                    out.extend_from_slice(&u64_val.to_le_bytes());
+                } else {
+                    // Negative and outside i32: no binary type here represents
+                    // it exactly, so fall back to the truncating int32 write.
+                    out.push(BINARY_TYPE_INT32);
+                    write_binary_cstring(out, k);
+                    out.extend_from_slice(&(*i as i32).to_le_bytes());
+                }
+            }
+            VdfValue::Float(f) => {
+                out.push(BINARY_TYPE_FLOAT32);
+                write_binary_cstring(out, k);
+                out.extend_from_slice(&(*f as f32).to_le_bytes());
+            }
+            VdfValue::UInt64(u) => {
+                out.push(BINARY_TYPE_UINT64);
+                write_binary_cstring(out, k);
+                out.extend_from_slice(&u.to_le_bytes());
+            }
+        }
+    }
+    out.push(BINARY_TYPE_END);
+}
+
+fn write_binary_cstring(out: &mut Vec<u8>, s: &str) {
+    out.extend_from_slice(s.as_bytes());
+    out.push(0);
+}